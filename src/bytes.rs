@@ -0,0 +1,152 @@
+pub mod bytes {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use value::Value;
+    use io::{Reader, SliceReader};
+    use error::{Error, Result};
+
+    #[cfg(test)]
+    use std::mem::transmute;
+    #[cfg(not(test))]
+    use core::mem::transmute;
+
+    /// A source of bytes that the shared numeric-decoding helpers below can read from,
+    /// regardless of whether the underlying reader hands back owned or borrowed bytes.
+    pub trait BytesRead {
+        type Bytes: AsRef<[u8]>;
+
+        fn read_byte(&mut self) -> Result<u8>;
+        fn read_n_bytes(&mut self, n: usize) -> Result<Self::Bytes>;
+    }
+
+    impl<R: Reader> BytesRead for R {
+        type Bytes = Vec<u8>;
+
+        fn read_byte(&mut self) -> Result<u8> {
+            Reader::read_byte(self)
+        }
+
+        fn read_n_bytes(&mut self, n: usize) -> Result<Vec<u8>> {
+            Reader::read_n_bytes(self, n)
+        }
+    }
+
+    impl<'a> BytesRead for SliceReader<'a> {
+        type Bytes = &'a [u8];
+
+        fn read_byte(&mut self) -> Result<u8> {
+            SliceReader::read_byte(self)
+        }
+
+        fn read_n_bytes(&mut self, n: usize) -> Result<&'a [u8]> {
+            SliceReader::read_n_bytes(self, n)
+        }
+    }
+
+    pub fn read_uint<B: BytesRead>(source: &mut B, additional_type: usize) -> Result<u64> {
+        match additional_type {
+            value @ 0b00000...0b10111 => Ok(value as u64),
+            0b11000 => Ok(source.read_byte()? as u64),
+            0b11001 => Ok(u8_slice_to_u16(source.read_n_bytes(2)?.as_ref()) as u64),
+            0b11010 => Ok(u8_slice_to_u32(source.read_n_bytes(4)?.as_ref()) as u64),
+            0b11011 => Ok(u8_slice_to_u64(source.read_n_bytes(8)?.as_ref())),
+            _ => Err(Error::InvalidAdditionalType(additional_type as u8)),
+        }
+    }
+
+    pub fn read_negative_int<B: BytesRead>(source: &mut B, additional_type: usize) -> Result<i64> {
+        let n = read_uint(source, additional_type)?;
+
+        if n > i64::max_value() as u64 {
+            return Err(Error::IntegerOverflow);
+        }
+
+        Ok(-1 - n as i64)
+    }
+
+    pub fn read_f16<B: BytesRead>(source: &mut B) -> Result<f64> {
+        let raw = source.read_n_bytes(2)?;
+        Ok(f16_to_f64(u8_slice_to_u16(raw.as_ref())))
+    }
+
+    pub fn read_f32<B: BytesRead>(source: &mut B) -> Result<f64> {
+        let raw = source.read_n_bytes(4)?;
+        let bits = u8_slice_to_u32(raw.as_ref());
+        let value: f32 = unsafe { transmute(bits) };
+        Ok(value as f64)
+    }
+
+    pub fn read_f64<B: BytesRead>(source: &mut B) -> Result<f64> {
+        let raw = source.read_n_bytes(8)?;
+        let bits = u8_slice_to_u64(raw.as_ref());
+        Ok(unsafe { transmute(bits) })
+    }
+
+    pub fn major_type(byte: u8) -> u8 {
+        byte >> 5
+    }
+
+    pub fn additional_type(byte: u8) -> u8 {
+        byte & 0b0001_1111
+    }
+
+    pub fn concat(major_type: u8, additional_type: u8) -> u8 {
+        (major_type << 5) | additional_type
+    }
+
+    pub fn to_string(bytes: &[u8]) -> Result<Value> {
+        match String::from_utf8(bytes.to_vec()) {
+            Ok(string) => Ok(Value::String(string)),
+            Err(_) => Err(Error::InvalidUtf8),
+        }
+    }
+
+    #[inline]
+    pub fn u8_slice_to_u16(slice: &[u8]) -> u16 {
+        ((slice[0] as u16) & 0xff) << 8 |
+        ((slice[1] as u16) & 0xff)
+    }
+
+    #[inline]
+    pub fn u8_slice_to_u32(slice: &[u8]) -> u32 {
+        ((slice[0] as u32) & 0xff) << 24 |
+        ((slice[1] as u32) & 0xff) << 16 |
+        ((slice[2] as u32) & 0xff) << 8 |
+        ((slice[3] as u32) & 0xff)
+    }
+
+    #[inline]
+    pub fn u8_slice_to_u64(slice: &[u8]) -> u64 {
+        ((slice[0] as u64) & 0xff) << 56 |
+        ((slice[1] as u64) & 0xff) << 48 |
+        ((slice[2] as u64) & 0xff) << 40 |
+        ((slice[3] as u64) & 0xff) << 32 |
+        ((slice[4] as u64) & 0xff) << 24 |
+        ((slice[5] as u64) & 0xff) << 16 |
+        ((slice[6] as u64) & 0xff) << 8 |
+        ((slice[7] as u64) & 0xff)
+    }
+
+    #[inline]
+    fn pow2_f64(exp: i32) -> f64 {
+        let bits: u64 = ((1023 + exp) as u64) << 52;
+        unsafe { transmute(bits) }
+    }
+
+    #[inline]
+    pub fn f16_to_f64(half: u16) -> f64 {
+        let sign = (half >> 15) & 1;
+        let exp = (half >> 10) & 0x1f;
+        let mant = half & 0x3ff;
+
+        let value = if exp == 0 {
+            mant as f64 * pow2_f64(-24)
+        } else if exp == 0x1f {
+            if mant == 0 { f64::INFINITY } else { f64::NAN }
+        } else {
+            (1.0 + mant as f64 / 1024.0) * pow2_f64(exp as i32 - 15)
+        };
+
+        if sign == 1 { -value } else { value }
+    }
+}
@@ -0,0 +1,3 @@
+pub const MAX_U8: u64 = 0xff;
+pub const MAX_U16: u64 = 0xffff;
+pub const MAX_U32: u64 = 0xffff_ffff;
@@ -1,128 +1,184 @@
+use alloc::boxed::Box;
 use alloc::btree_map::BTreeMap;
+use alloc::string::String;
 use alloc::vec::Vec;
 use value::Value;
 use bytes::bytes;
 use io::{Reader, VecReader};
-
-#[cfg(test)]
-use std::mem::transmute;
-#[cfg(not(test))]
-use core::mem::transmute;
+use error::{Error, Result};
 
 pub struct Deserializer<R: Reader>  {
     pub reader: R,
 }
 
-pub fn from_bytes(bytes: Vec<u8>) -> Value
+pub fn from_bytes(bytes: Vec<u8>) -> Result<Value>
 {
     let vec_reader = VecReader{input: bytes};
     let mut d = Deserializer::from_vec_reader(vec_reader);
-    d.parse_value()
-}
+    let value = d.parse_value()?;
 
-pub enum TestValue {
-    Int(u64),
-}
+    if !d.reader.is_empty() {
+        return Err(Error::TrailingData);
+    }
 
-#[inline]
-fn u8_slice_to_u16(slice: &[u8]) -> u16 {
-        ((slice[0] as u16) & 0xff) << 8 |
-        ((slice[1] as u16) & 0xff)
+    Ok(value)
 }
 
-#[inline]
-fn u8_slice_to_u32(slice: &[u8]) -> u32 {
-        ((slice[0] as u32) & 0xff) << 24 |
-        ((slice[1] as u32) & 0xff) << 16 |
-        ((slice[2] as u32) & 0xff) << 8 |
-        ((slice[3] as u32) & 0xff)
+pub enum TestValue {
+    Int(u64),
 }
 
-
 impl<R> Deserializer<R> where R: Reader {
     fn from_vec_reader(bytes: R) -> Deserializer<R> {
         Deserializer{reader: bytes}
     }
 
-    fn parse_value(&mut self) -> Value
+    fn parse_value(&mut self) -> Result<Value>
     {
-        let header_byte = self.reader.read_byte();
+        let header_byte = self.reader.read_byte()?;
         let major_type = bytes::major_type(header_byte);
         let additional_type = bytes::additional_type(header_byte) as usize;
 
         match major_type {
             0b000 => self.deserialize_int(additional_type),
+            0b001 => self.deserialize_negative_int(additional_type),
             0b010 => self.deserialize_bytes(additional_type),
             0b011 => self.deserialize_string(additional_type),
             0b100 => self.deserialize_array(additional_type),
             0b101 => self.deserialize_map(additional_type),
+            0b110 => self.deserialize_tag(additional_type),
             0b111 => self.deserialize_simple(additional_type),
-            _ => unreachable!(),
+            _ => Err(Error::UnknownMajorType(major_type)),
         }
     }
 
-    fn read_additional_type(&mut self, additional_type: u8) -> usize {
-        match additional_type {
-            0b00000...0b10111 => additional_type as usize,
-            0b11000 => self.reader.read_byte() as usize,
-            _ => unreachable!(),
-        }
+    fn deserialize_int(&mut self, additional_type: usize) -> Result<Value>{
+        Ok(Value::Int(self.read_uint(additional_type)?))
     }
 
-    fn deserialize_int(&mut self, additional_type: usize) -> Value{
-        match additional_type {
-            value @ 0b00000...0b10111 => Value::Int(value as u32),
-            0b11000 => self.read_u8(),
-            0b11001 => self.read_u16(),
-            0b11010 => self.read_u32(),
-            _ => unreachable!(),
-        }
+    fn deserialize_negative_int(&mut self, additional_type: usize) -> Result<Value>{
+        Ok(Value::NegInt(bytes::read_negative_int(&mut self.reader, additional_type)?))
     }
 
-    fn read_u8(&mut self) -> Value{
-        Value::Int(self.reader.read_byte() as u32)
+    fn read_uint(&mut self, additional_type: usize) -> Result<u64> {
+        bytes::read_uint(&mut self.reader, additional_type)
     }
 
-    fn read_u16(&mut self) -> Value{
-        let bytes = self.reader.read_n_bytes(2);
-        Value::Int(u8_slice_to_u16(bytes.as_slice()) as u32)
+    fn deserialize_bytes(&mut self, additional_type: usize) -> Result<Value>{
+        if additional_type == 0b11111 {
+            return self.deserialize_indefinite_bytes();
+        }
+
+        let len = self.read_uint(additional_type)? as usize;
+        Ok(Value::Bytes(self.reader.read_n_bytes(len)?))
     }
 
-    fn read_u32(&mut self) -> Value{
-        let bytes = self.reader.read_n_bytes(4);
-        Value::Int(u8_slice_to_u32(bytes.as_slice()) as u32)
+    fn deserialize_indefinite_bytes(&mut self) -> Result<Value> {
+        let mut result = Vec::new();
+
+        while self.reader.peek_byte()? != 0xff {
+            match self.parse_value()? {
+                Value::Bytes(chunk) => result.extend(chunk),
+                _ => return Err(Error::InvalidIndefiniteChunk),
+            }
+        }
+        self.reader.read_byte()?;
+
+        Ok(Value::Bytes(result))
     }
 
-    fn deserialize_bytes(&mut self, len: usize) -> Value{
-        Value::Bytes(self.reader.read_n_bytes(len as usize))
+    fn deserialize_string(&mut self, additional_type: usize) -> Result<Value> {
+        if additional_type == 0b11111 {
+            return self.deserialize_indefinite_string();
+        }
+
+        let len = self.read_uint(additional_type)? as usize;
+        let bytes = self.reader.read_n_bytes(len)?;
+        bytes::to_string(&bytes)
     }
 
-    fn deserialize_string(&mut self, len: usize) -> Value {
-        bytes::to_string(&self.reader.read_n_bytes(len as usize))
+    fn deserialize_indefinite_string(&mut self) -> Result<Value> {
+        let mut result = String::new();
+
+        while self.reader.peek_byte()? != 0xff {
+            match self.parse_value()? {
+                Value::String(chunk) => result.push_str(&chunk),
+                _ => return Err(Error::InvalidIndefiniteChunk),
+            }
+        }
+        self.reader.read_byte()?;
+
+        Ok(Value::String(result))
     }
 
-    fn deserialize_array(&mut self, len: usize) -> Value {
-        let values = (0..len).map(|_| self.parse_value()).collect();
-        Value::Array(values)
+    fn deserialize_array(&mut self, additional_type: usize) -> Result<Value> {
+        if additional_type == 0b11111 {
+            return self.deserialize_indefinite_array();
+        }
+
+        let len = self.read_uint(additional_type)? as usize;
+        let values: Result<Vec<Value>> = (0..len).map(|_| self.parse_value()).collect();
+        Ok(Value::Array(values?))
     }
 
-    fn deserialize_map(&mut self, len: usize) -> Value {
+    fn deserialize_indefinite_array(&mut self) -> Result<Value> {
+        let mut values = Vec::new();
+
+        while self.reader.peek_byte()? != 0xff {
+            values.push(self.parse_value()?);
+        }
+        self.reader.read_byte()?;
+
+        Ok(Value::Array(values))
+    }
+
+    fn deserialize_map(&mut self, additional_type: usize) -> Result<Value> {
+        if additional_type == 0b11111 {
+            return self.deserialize_indefinite_map();
+        }
+
+        let len = self.read_uint(additional_type)? as usize;
         let mut map = BTreeMap::new();
 
         for _ in 0..len {
-            let key = self.parse_value().as_string().unwrap().clone();
-            let value = self.parse_value();
+            let key = self.parse_value()?;
+            let value = self.parse_value()?;
+
+            map.insert(key, value);
+        }
+
+        Ok(Value::Map(map))
+    }
+
+    fn deserialize_indefinite_map(&mut self) -> Result<Value> {
+        let mut map = BTreeMap::new();
+
+        while self.reader.peek_byte()? != 0xff {
+            let key = self.parse_value()?;
+            let value = self.parse_value()?;
 
             map.insert(key, value);
         }
+        self.reader.read_byte()?;
+
+        Ok(Value::Map(map))
+    }
 
-        Value::Map(map)
+    fn deserialize_tag(&mut self, additional_type: usize) -> Result<Value> {
+        let tag = self.read_uint(additional_type)?;
+        let value = self.parse_value()?;
+        Ok(Value::Tag(tag, Box::new(value)))
     }
 
-    fn deserialize_simple(&mut self, value: usize) -> Value {
+    fn deserialize_simple(&mut self, value: usize) -> Result<Value> {
         match value {
-            22 => Value::Null,
-            _ => unreachable!(),
+            20 => Ok(Value::Bool(false)),
+            21 => Ok(Value::Bool(true)),
+            22 => Ok(Value::Null),
+            25 => Ok(Value::Float(bytes::read_f16(&mut self.reader)?)),
+            26 => Ok(Value::Float(bytes::read_f32(&mut self.reader)?)),
+            27 => Ok(Value::Float(bytes::read_f64(&mut self.reader)?)),
+            _ => Err(Error::InvalidAdditionalType(value as u8)),
         }
     }
 }
@@ -130,56 +186,174 @@ impl<R> Deserializer<R> where R: Reader {
 #[test]
 fn deserialize_map() {
     let mut test_map = BTreeMap::new();
-    test_map.insert("key1".into(), Value::String("value1".into()));
-    test_map.insert("key2".into(), Value::String("value2".into()));
+    test_map.insert(Value::String("key1".into()), Value::String("value1".into()));
+    test_map.insert(Value::String("key2".into()), Value::String("value2".into()));
     let expected: Value = Value::Map(test_map);
-    assert_eq!(expected, from_bytes(vec![0xa2, 0x64, 0x6b, 0x65, 0x79, 0x31, 0x66, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x31, 0x64, 0x6b, 0x65, 0x79, 0x32, 0x66, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x32]));
+    assert_eq!(expected, from_bytes(vec![0xa2, 0x64, 0x6b, 0x65, 0x79, 0x31, 0x66, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x31, 0x64, 0x6b, 0x65, 0x79, 0x32, 0x66, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x32]).unwrap());
 }
 
 #[test]
 fn deserialize_string() {
     let expected: Value = Value::String("test".into());
-    assert_eq!(expected, from_bytes(vec![0x64, 0x74, 0x65, 0x73, 0x74]));
+    assert_eq!(expected, from_bytes(vec![0x64, 0x74, 0x65, 0x73, 0x74]).unwrap());
 }
 
 #[test]
 fn deserialize_array() {
     let expected: Value = Value::Array(vec![Value::Int(1), Value::Int(2), Value::Int(3)]);
-    assert_eq!(expected, from_bytes(vec![0x83, 0x01, 0x02, 0x03]));
+    assert_eq!(expected, from_bytes(vec![0x83, 0x01, 0x02, 0x03]).unwrap());
 }
 
 #[test]
 fn deserialize_bytes() {
     let expected: Value = Value::Bytes(vec![1, 2, 3]);
-    assert_eq!(expected, from_bytes(vec![0x43, 0x01, 0x02, 0x03]));
+    assert_eq!(expected, from_bytes(vec![0x43, 0x01, 0x02, 0x03]).unwrap());
 }
 
 #[test]
 fn deserialize_u8() {
     let expected: Value = Value::Int(1);
-    assert_eq!(expected, from_bytes(vec![0x01]));
+    assert_eq!(expected, from_bytes(vec![0x01]).unwrap());
 }
 
 #[test]
 fn deserialize_u8_2() {
     let expected: Value = Value::Int(42);
-    assert_eq!(expected, from_bytes(vec![24, 42]));
+    assert_eq!(expected, from_bytes(vec![24, 42]).unwrap());
 }
 
 #[test]
 fn deserialize_u16() {
     let expected: Value = Value::Int(0x100);
-    assert_eq!(expected, from_bytes(vec![25, 1, 0]));
+    assert_eq!(expected, from_bytes(vec![25, 1, 0]).unwrap());
 }
 
 #[test]
 fn deserialize_u32() {
     let expected: Value = Value::Int(0x1000000);
-    assert_eq!(expected, from_bytes(vec![26, 1, 0, 0, 0]));
+    assert_eq!(expected, from_bytes(vec![26, 1, 0, 0, 0]).unwrap());
+}
+
+#[test]
+fn deserialize_u64() {
+    let expected: Value = Value::Int(0x100000000);
+    assert_eq!(expected, from_bytes(vec![27, 0, 0, 0, 1, 0, 0, 0, 0]).unwrap());
+}
+
+#[test]
+fn deserialize_negative_int() {
+    let expected: Value = Value::NegInt(-1);
+    assert_eq!(expected, from_bytes(vec![0x20]).unwrap());
+}
+
+#[test]
+fn deserialize_negative_int_u16() {
+    let expected: Value = Value::NegInt(-0x101);
+    assert_eq!(expected, from_bytes(vec![0x39, 1, 0]).unwrap());
+}
+
+#[test]
+fn deserialize_negative_int_overflow() {
+    assert_eq!(
+        Err(Error::IntegerOverflow),
+        from_bytes(vec![0x3b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff])
+    );
 }
 
 #[test]
 fn deserialize_null() {
     let expected: Value = Value::Null;
-    assert_eq!(expected, from_bytes(vec![246]));
+    assert_eq!(expected, from_bytes(vec![246]).unwrap());
+}
+
+#[test]
+fn deserialize_bool() {
+    assert_eq!(Value::Bool(false), from_bytes(vec![0xf4]).unwrap());
+    assert_eq!(Value::Bool(true), from_bytes(vec![0xf5]).unwrap());
+}
+
+#[test]
+fn deserialize_f64() {
+    let expected: Value = Value::Float(1.1);
+    assert_eq!(expected, from_bytes(vec![0xfb, 0x3f, 0xf1, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a]).unwrap());
+}
+
+#[test]
+fn deserialize_f32() {
+    let expected: Value = Value::Float(100000.0);
+    assert_eq!(expected, from_bytes(vec![0xfa, 0x47, 0xc3, 0x50, 0x00]).unwrap());
+}
+
+#[test]
+fn deserialize_f16() {
+    let expected: Value = Value::Float(1.5);
+    assert_eq!(expected, from_bytes(vec![0xf9, 0x3e, 0x00]).unwrap());
+}
+
+#[test]
+fn deserialize_f16_infinity() {
+    let expected: Value = Value::Float(f64::INFINITY);
+    assert_eq!(expected, from_bytes(vec![0xf9, 0x7c, 0x00]).unwrap());
+}
+
+#[test]
+fn deserialize_map_with_integer_keys() {
+    let mut test_map = BTreeMap::new();
+    test_map.insert(Value::Int(1), Value::String("a".into()));
+    test_map.insert(Value::Int(2), Value::String("b".into()));
+    let expected: Value = Value::Map(test_map);
+    assert_eq!(expected, from_bytes(vec![0xa2, 0x01, 0x61, 0x61, 0x02, 0x61, 0x62]).unwrap());
+}
+
+#[test]
+fn deserialize_indefinite_array() {
+    let expected: Value = Value::Array(vec![Value::Int(1), Value::Int(2)]);
+    assert_eq!(expected, from_bytes(vec![0x9f, 0x01, 0x02, 0xff]).unwrap());
+}
+
+#[test]
+fn deserialize_indefinite_map() {
+    let mut test_map = BTreeMap::new();
+    test_map.insert(Value::String("a".into()), Value::Int(1));
+    test_map.insert(Value::String("b".into()), Value::Int(2));
+    let expected: Value = Value::Map(test_map);
+    assert_eq!(expected, from_bytes(vec![0xbf, 0x61, 0x61, 0x01, 0x61, 0x62, 0x02, 0xff]).unwrap());
+}
+
+#[test]
+fn deserialize_indefinite_bytes() {
+    let expected: Value = Value::Bytes(vec![1, 2, 3, 4]);
+    assert_eq!(expected, from_bytes(vec![0x5f, 0x42, 0x01, 0x02, 0x42, 0x03, 0x04, 0xff]).unwrap());
+}
+
+#[test]
+fn deserialize_indefinite_string() {
+    let expected: Value = Value::String("streaming".into());
+    assert_eq!(expected, from_bytes(vec![
+        0x7f, 0x65, 0x73, 0x74, 0x72, 0x65, 0x61, 0x64, 0x6d, 0x69, 0x6e, 0x67, 0xff,
+    ]).unwrap());
+}
+
+#[test]
+fn deserialize_unexpected_eof() {
+    assert_eq!(Err(Error::UnexpectedEof), from_bytes(vec![0x64, 0x74, 0x65]));
+}
+
+#[test]
+fn deserialize_tag() {
+    let expected: Value = Value::Tag(0, Box::new(Value::String("2013-03-21T20:04:00Z".into())));
+    assert_eq!(expected, from_bytes(vec![
+        0xc0, 0x74, 0x32, 0x30, 0x31, 0x33, 0x2d, 0x30, 0x33, 0x2d, 0x32, 0x31, 0x54, 0x32, 0x30,
+        0x3a, 0x30, 0x34, 0x3a, 0x30, 0x30, 0x5a,
+    ]).unwrap());
+}
+
+#[test]
+fn deserialize_trailing_data() {
+    assert_eq!(Err(Error::TrailingData), from_bytes(vec![0x01, 0x02]));
+}
+
+#[test]
+fn deserialize_invalid_utf8() {
+    assert_eq!(Err(Error::InvalidUtf8), from_bytes(vec![0x61, 0xff]));
 }
@@ -0,0 +1,254 @@
+use alloc::boxed::Box;
+use alloc::btree_map::BTreeMap;
+use alloc::vec::Vec;
+use value_ref::ValueRef;
+use bytes::bytes;
+use io::SliceReader;
+use error::{Error, Result};
+
+pub struct Deserializer<'a> {
+    pub reader: SliceReader<'a>,
+}
+
+pub fn from_slice<'a>(input: &'a [u8]) -> Result<ValueRef<'a>> {
+    let mut d = Deserializer::from_slice_reader(SliceReader::new(input));
+    let value = d.parse_value()?;
+
+    if !d.reader.is_empty() {
+        return Err(Error::TrailingData);
+    }
+
+    Ok(value)
+}
+
+impl<'a> Deserializer<'a> {
+    fn from_slice_reader(reader: SliceReader<'a>) -> Deserializer<'a> {
+        Deserializer{reader: reader}
+    }
+
+    fn parse_value(&mut self) -> Result<ValueRef<'a>> {
+        let header_byte = self.reader.read_byte()?;
+        let major_type = bytes::major_type(header_byte);
+        let additional_type = bytes::additional_type(header_byte) as usize;
+
+        match major_type {
+            0b000 => self.deserialize_int(additional_type),
+            0b001 => self.deserialize_negative_int(additional_type),
+            0b010 => self.deserialize_bytes(additional_type),
+            0b011 => self.deserialize_string(additional_type),
+            0b100 => self.deserialize_array(additional_type),
+            0b101 => self.deserialize_map(additional_type),
+            0b110 => self.deserialize_tag(additional_type),
+            0b111 => self.deserialize_simple(additional_type),
+            _ => Err(Error::UnknownMajorType(major_type)),
+        }
+    }
+
+    fn deserialize_int(&mut self, additional_type: usize) -> Result<ValueRef<'a>> {
+        Ok(ValueRef::Int(self.read_uint(additional_type)?))
+    }
+
+    fn deserialize_negative_int(&mut self, additional_type: usize) -> Result<ValueRef<'a>> {
+        Ok(ValueRef::NegInt(bytes::read_negative_int(&mut self.reader, additional_type)?))
+    }
+
+    fn read_uint(&mut self, additional_type: usize) -> Result<u64> {
+        bytes::read_uint(&mut self.reader, additional_type)
+    }
+
+    fn deserialize_bytes(&mut self, additional_type: usize) -> Result<ValueRef<'a>> {
+        if additional_type == 0b11111 {
+            return Err(Error::InvalidIndefiniteChunk);
+        }
+
+        let len = self.read_uint(additional_type)? as usize;
+        Ok(ValueRef::Bytes(self.reader.read_n_bytes(len)?))
+    }
+
+    fn deserialize_string(&mut self, additional_type: usize) -> Result<ValueRef<'a>> {
+        if additional_type == 0b11111 {
+            return Err(Error::InvalidIndefiniteChunk);
+        }
+
+        let len = self.read_uint(additional_type)? as usize;
+        let bytes = self.reader.read_n_bytes(len)?;
+        match ::core::str::from_utf8(bytes) {
+            Ok(string) => Ok(ValueRef::String(string)),
+            Err(_) => Err(Error::InvalidUtf8),
+        }
+    }
+
+    fn deserialize_array(&mut self, additional_type: usize) -> Result<ValueRef<'a>> {
+        if additional_type == 0b11111 {
+            return self.deserialize_indefinite_array();
+        }
+
+        let len = self.read_uint(additional_type)? as usize;
+        let values: Result<Vec<ValueRef<'a>>> = (0..len).map(|_| self.parse_value()).collect();
+        Ok(ValueRef::Array(values?))
+    }
+
+    fn deserialize_indefinite_array(&mut self) -> Result<ValueRef<'a>> {
+        let mut values = Vec::new();
+
+        while self.reader.peek_byte()? != 0xff {
+            values.push(self.parse_value()?);
+        }
+        self.reader.read_byte()?;
+
+        Ok(ValueRef::Array(values))
+    }
+
+    fn deserialize_map(&mut self, additional_type: usize) -> Result<ValueRef<'a>> {
+        if additional_type == 0b11111 {
+            return self.deserialize_indefinite_map();
+        }
+
+        let len = self.read_uint(additional_type)? as usize;
+        let mut map = BTreeMap::new();
+
+        for _ in 0..len {
+            let key = self.parse_value()?;
+            let value = self.parse_value()?;
+
+            map.insert(key, value);
+        }
+
+        Ok(ValueRef::Map(map))
+    }
+
+    fn deserialize_indefinite_map(&mut self) -> Result<ValueRef<'a>> {
+        let mut map = BTreeMap::new();
+
+        while self.reader.peek_byte()? != 0xff {
+            let key = self.parse_value()?;
+            let value = self.parse_value()?;
+
+            map.insert(key, value);
+        }
+        self.reader.read_byte()?;
+
+        Ok(ValueRef::Map(map))
+    }
+
+    fn deserialize_tag(&mut self, additional_type: usize) -> Result<ValueRef<'a>> {
+        let tag = self.read_uint(additional_type)?;
+        let value = self.parse_value()?;
+        Ok(ValueRef::Tag(tag, Box::new(value)))
+    }
+
+    fn deserialize_simple(&mut self, value: usize) -> Result<ValueRef<'a>> {
+        match value {
+            20 => Ok(ValueRef::Bool(false)),
+            21 => Ok(ValueRef::Bool(true)),
+            22 => Ok(ValueRef::Null),
+            25 => Ok(ValueRef::Float(bytes::read_f16(&mut self.reader)?)),
+            26 => Ok(ValueRef::Float(bytes::read_f32(&mut self.reader)?)),
+            27 => Ok(ValueRef::Float(bytes::read_f64(&mut self.reader)?)),
+            _ => Err(Error::InvalidAdditionalType(value as u8)),
+        }
+    }
+}
+
+#[test]
+fn deserialize_string() {
+    let expected = ValueRef::String("test");
+    assert_eq!(expected, from_slice(&[0x64, 0x74, 0x65, 0x73, 0x74]).unwrap());
+}
+
+#[test]
+fn deserialize_string_borrows_input() {
+    let input = [0x64, 0x74, 0x65, 0x73, 0x74];
+    match from_slice(&input).unwrap() {
+        ValueRef::String(string) => assert_eq!(string.as_ptr(), input[1..].as_ptr()),
+        _ => panic!("expected ValueRef::String"),
+    }
+}
+
+#[test]
+fn deserialize_bytes() {
+    let expected = ValueRef::Bytes(&[1, 2, 3]);
+    assert_eq!(expected, from_slice(&[0x43, 0x01, 0x02, 0x03]).unwrap());
+}
+
+#[test]
+fn deserialize_bytes_borrows_input() {
+    let input = [0x43, 0x01, 0x02, 0x03];
+    match from_slice(&input).unwrap() {
+        ValueRef::Bytes(bytes) => assert_eq!(bytes.as_ptr(), input[1..].as_ptr()),
+        _ => panic!("expected ValueRef::Bytes"),
+    }
+}
+
+#[test]
+fn deserialize_array() {
+    let expected = ValueRef::Array(vec![ValueRef::Int(1), ValueRef::Int(2), ValueRef::Int(3)]);
+    assert_eq!(expected, from_slice(&[0x83, 0x01, 0x02, 0x03]).unwrap());
+}
+
+#[test]
+fn deserialize_map() {
+    let mut expected_map = BTreeMap::new();
+    expected_map.insert(ValueRef::String("key1"), ValueRef::String("value1"));
+    expected_map.insert(ValueRef::String("key2"), ValueRef::String("value2"));
+    let expected = ValueRef::Map(expected_map);
+    assert_eq!(expected, from_slice(&[
+        0xa2, 0x64, 0x6b, 0x65, 0x79, 0x31, 0x66, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x31,
+        0x64, 0x6b, 0x65, 0x79, 0x32, 0x66, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x32,
+    ]).unwrap());
+}
+
+#[test]
+fn deserialize_negative_int() {
+    let expected = ValueRef::NegInt(-1);
+    assert_eq!(expected, from_slice(&[0x20]).unwrap());
+}
+
+#[test]
+fn deserialize_negative_int_overflow() {
+    assert_eq!(
+        Err(Error::IntegerOverflow),
+        from_slice(&[0x3b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff])
+    );
+}
+
+#[test]
+fn deserialize_bool() {
+    assert_eq!(ValueRef::Bool(false), from_slice(&[0xf4]).unwrap());
+    assert_eq!(ValueRef::Bool(true), from_slice(&[0xf5]).unwrap());
+}
+
+#[test]
+fn deserialize_f64() {
+    let expected = ValueRef::Float(1.1);
+    assert_eq!(expected, from_slice(&[0xfb, 0x3f, 0xf1, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a]).unwrap());
+}
+
+#[test]
+fn deserialize_tag() {
+    let expected = ValueRef::Tag(0, Box::new(ValueRef::String("2013-03-21T20:04:00Z")));
+    assert_eq!(expected, from_slice(&[
+        0xc0, 0x74, 0x32, 0x30, 0x31, 0x33, 0x2d, 0x30, 0x33, 0x2d, 0x32, 0x31, 0x54, 0x32, 0x30,
+        0x3a, 0x30, 0x34, 0x3a, 0x30, 0x30, 0x5a,
+    ]).unwrap());
+}
+
+#[test]
+fn deserialize_indefinite_bytes_is_invalid() {
+    assert_eq!(Err(Error::InvalidIndefiniteChunk), from_slice(&[0x5f, 0x42, 0x01, 0x02, 0xff]));
+}
+
+#[test]
+fn deserialize_unexpected_eof() {
+    assert_eq!(Err(Error::UnexpectedEof), from_slice(&[0x64, 0x74, 0x65]));
+}
+
+#[test]
+fn deserialize_trailing_data() {
+    assert_eq!(Err(Error::TrailingData), from_slice(&[0x01, 0x02]));
+}
+
+#[test]
+fn deserialize_invalid_utf8() {
+    assert_eq!(Err(Error::InvalidUtf8), from_slice(&[0x61, 0xff]));
+}
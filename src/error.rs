@@ -0,0 +1,12 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    UnexpectedEof,
+    UnknownMajorType(u8),
+    InvalidAdditionalType(u8),
+    InvalidIndefiniteChunk,
+    InvalidUtf8,
+    TrailingData,
+    IntegerOverflow,
+}
+
+pub type Result<T> = ::core::result::Result<T, Error>;
@@ -0,0 +1,87 @@
+use alloc::vec::Vec;
+use error::{Error, Result};
+
+pub trait Reader {
+    fn read_byte(&mut self) -> Result<u8>;
+    fn read_n_bytes(&mut self, n: usize) -> Result<Vec<u8>>;
+    fn peek_byte(&self) -> Result<u8>;
+    fn is_empty(&self) -> bool;
+}
+
+pub trait Writer {
+    fn write_bytes(&mut self, bytes: Vec<u8>);
+}
+
+pub struct VecReader {
+    pub input: Vec<u8>,
+}
+
+impl Reader for VecReader {
+    fn read_byte(&mut self) -> Result<u8> {
+        if self.input.is_empty() {
+            return Err(Error::UnexpectedEof);
+        }
+
+        Ok(self.input.remove(0))
+    }
+
+    fn read_n_bytes(&mut self, n: usize) -> Result<Vec<u8>> {
+        if self.input.len() < n {
+            return Err(Error::UnexpectedEof);
+        }
+
+        Ok(self.input.drain(0..n).collect())
+    }
+
+    fn peek_byte(&self) -> Result<u8> {
+        self.input.first().cloned().ok_or(Error::UnexpectedEof)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.input.is_empty()
+    }
+}
+
+pub struct VecWriter<'a> {
+    pub output: &'a mut Vec<u8>,
+}
+
+impl<'a> Writer for VecWriter<'a> {
+    fn write_bytes(&mut self, bytes: Vec<u8>) {
+        self.output.extend(bytes);
+    }
+}
+
+pub struct SliceReader<'a> {
+    pub input: &'a [u8],
+}
+
+impl<'a> SliceReader<'a> {
+    pub fn new(input: &'a [u8]) -> SliceReader<'a> {
+        SliceReader{input: input}
+    }
+
+    pub fn read_byte(&mut self) -> Result<u8> {
+        let byte = self.peek_byte()?;
+        self.input = &self.input[1..];
+        Ok(byte)
+    }
+
+    pub fn read_n_bytes(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.input.len() < n {
+            return Err(Error::UnexpectedEof);
+        }
+
+        let (bytes, rest) = self.input.split_at(n);
+        self.input = rest;
+        Ok(bytes)
+    }
+
+    pub fn peek_byte(&self) -> Result<u8> {
+        self.input.first().cloned().ok_or(Error::UnexpectedEof)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.input.is_empty()
+    }
+}
@@ -0,0 +1,22 @@
+#![no_std]
+
+extern crate alloc;
+#[cfg(test)]
+extern crate std;
+#[cfg(feature = "serde")]
+extern crate serde;
+
+#[macro_use]
+mod value_cmp;
+
+pub mod bytes;
+pub mod constants;
+pub mod de;
+pub mod de_ref;
+pub mod error;
+pub mod io;
+pub mod ser;
+#[cfg(feature = "serde")]
+pub mod serde_support;
+pub mod value;
+pub mod value_ref;
@@ -1,4 +1,6 @@
 #[cfg(test)]
+use alloc::boxed::Box;
+#[cfg(test)]
 use alloc::btree_map::BTreeMap;
 use alloc::string::String;
 use constants::*;
@@ -7,13 +9,23 @@ use alloc::vec::Vec;
 use value::{Value};
 use io::{Writer, VecWriter};
 
+#[cfg(test)]
+use std::mem::transmute;
+#[cfg(not(test))]
+use core::mem::transmute;
+
 pub trait Serializer {
-    fn serialize_unsigned(&mut self, n: usize, major_type: u8);
+    fn serialize_unsigned(&mut self, n: u64, major_type: u8);
     fn serialize_bytes(&mut self, bytes: Vec<u8>);
     fn serialize_seq(&mut self, len: usize);
     fn serialize_map(&mut self, len: usize);
     fn serialize_simple(&mut self, value: usize);
     fn serialize_string(&mut self, string: &String);
+    fn serialize_float(&mut self, value: f64);
+    fn serialize_tag(&mut self, tag: u64);
+    fn serialize_indefinite_seq_start(&mut self);
+    fn serialize_indefinite_map_start(&mut self);
+    fn serialize_break(&mut self);
 }
 
 pub trait Serialize {
@@ -37,23 +49,102 @@ pub fn to_bytes(mut value: Value) -> Vec<u8>
     output
 }
 
+pub fn to_bytes_canonical(value: Value) -> Vec<u8> {
+    encode_canonical(&value)
+}
+
+fn encode_canonical(value: &Value) -> Vec<u8> {
+    match *value {
+        Value::Array(ref values) => {
+            let mut output = Vec::with_capacity(128);
+            {
+                let mut serializer = VecSerializer::from_vec_writer(VecWriter{output: &mut output});
+                serializer.serialize_seq(values.len());
+                for value in values {
+                    serializer.writer.write_bytes(encode_canonical(value));
+                }
+            }
+            output
+        },
+        Value::Map(ref map) => {
+            let mut pairs: Vec<(Vec<u8>, Vec<u8>)> = map.iter()
+                .map(|(key, value)| (encode_canonical(key), encode_canonical(value)))
+                .collect();
+            pairs.sort_by(|a, b| a.0.len().cmp(&b.0.len()).then_with(|| a.0.cmp(&b.0)));
+
+            let mut output = Vec::with_capacity(128);
+            {
+                let mut serializer = VecSerializer::from_vec_writer(VecWriter{output: &mut output});
+                serializer.serialize_map(pairs.len());
+                for (key_bytes, value_bytes) in pairs {
+                    serializer.writer.write_bytes(key_bytes);
+                    serializer.writer.write_bytes(value_bytes);
+                }
+            }
+            output
+        },
+        Value::Tag(tag, ref inner) => {
+            let mut output = Vec::with_capacity(128);
+            {
+                let mut serializer = VecSerializer::from_vec_writer(VecWriter{output: &mut output});
+                serializer.serialize_tag(tag);
+                serializer.writer.write_bytes(encode_canonical(inner));
+            }
+            output
+        },
+        _ => to_bytes(value.clone()),
+    }
+}
+
 impl<'a> VecSerializer<'a> {
     fn from_vec_writer(vec_writer: VecWriter) -> VecSerializer {
         VecSerializer{writer: vec_writer}
     }
 
-    fn encode_unsigned(&mut self, n: usize, major_type: u8) -> Vec<u8> {
+    fn encode_unsigned(&mut self, n: u64, major_type: u8) -> Vec<u8> {
         match n {
             n @ 0 ... 23 => vec![bytes::concat(major_type, n as u8)],
             n @ 24 ... MAX_U8 => vec![bytes::concat(major_type, 24), n as u8],
-            _ => unreachable!()
+            n if n <= MAX_U16 => {
+                let mut result = vec![bytes::concat(major_type, 25)];
+                result.extend_from_slice(&u16_to_u8_slice(n as u16));
+                result
+            },
+            n if n <= MAX_U32 => {
+                let mut result = vec![bytes::concat(major_type, 26)];
+                result.extend_from_slice(&u32_to_u8_slice(n as u32));
+                result
+            },
+            n => {
+                let mut result = vec![bytes::concat(major_type, 27)];
+                result.extend_from_slice(&u64_to_u8_slice(n));
+                result
+            },
         }
     }
 
 }
 
+#[inline]
+fn u16_to_u8_slice(n: u16) -> [u8; 2] {
+    [(n >> 8) as u8, n as u8]
+}
+
+#[inline]
+fn u32_to_u8_slice(n: u32) -> [u8; 4] {
+    [(n >> 24) as u8, (n >> 16) as u8, (n >> 8) as u8, n as u8]
+}
+
+#[inline]
+fn u64_to_u8_slice(n: u64) -> [u8; 8] {
+    [
+        (n >> 56) as u8, (n >> 48) as u8, (n >> 40) as u8, (n >> 32) as u8,
+        (n >> 24) as u8, (n >> 16) as u8, (n >> 8) as u8, n as u8,
+    ]
+}
+
 impl<'a> Serializer for VecSerializer<'a> {
-    fn serialize_unsigned(&mut self, n: usize, major_type: u8) {
+    fn serialize_unsigned(&mut self, n: u64, major_type: u8) {
         let bytes = self.encode_unsigned(n, major_type).to_vec();
         self.writer.write_bytes(bytes);
     }
@@ -63,32 +154,72 @@ impl<'a> Serializer for VecSerializer<'a> {
     }
 
     fn serialize_string(&mut self, string: &String) {
-        self.serialize_unsigned(string.len(), 0b011);
+        self.serialize_unsigned(string.len() as u64, 0b011);
         self.writer.write_bytes(string.as_bytes().to_vec());
     }
 
     fn serialize_seq(&mut self, len: usize) {
-        self.serialize_unsigned(len, 0b100);
+        self.serialize_unsigned(len as u64, 0b100);
     }
 
     fn serialize_map(&mut self, len: usize) {
-        self.serialize_unsigned(len, 0b101);
+        self.serialize_unsigned(len as u64, 0b101);
     }
 
     fn serialize_simple(&mut self, value: usize) {
-        self.serialize_unsigned(value, 0b111);
+        self.serialize_unsigned(value as u64, 0b111);
+    }
+
+    fn serialize_float(&mut self, value: f64) {
+        let as_f32 = value as f32;
+
+        if as_f32 as f64 == value {
+            let bits: u32 = unsafe { transmute(as_f32) };
+            self.writer.write_bytes(vec![bytes::concat(0b111, 26)]);
+            self.writer.write_bytes(u32_to_u8_slice(bits).to_vec());
+        } else {
+            let bits: u64 = unsafe { transmute(value) };
+            self.writer.write_bytes(vec![bytes::concat(0b111, 27)]);
+            self.writer.write_bytes(u64_to_u8_slice(bits).to_vec());
+        }
+    }
+
+    fn serialize_tag(&mut self, tag: u64) {
+        let bytes = self.encode_unsigned(tag, 0b110);
+        self.writer.write_bytes(bytes);
+    }
+
+    fn serialize_indefinite_seq_start(&mut self) {
+        self.writer.write_bytes(vec![bytes::concat(0b100, 0b11111)]);
+    }
+
+    fn serialize_indefinite_map_start(&mut self) {
+        self.writer.write_bytes(vec![bytes::concat(0b101, 0b11111)]);
+    }
+
+    fn serialize_break(&mut self) {
+        self.writer.write_bytes(vec![0xff]);
     }
 }
 
 #[test]
 fn serialize_map() {
     let mut test_map = BTreeMap::new();
-    test_map.insert("key1".into(), Value::String("value1".into()));
-    test_map.insert("key2".into(), Value::String("value2".into()));
+    test_map.insert(Value::String("key1".into()), Value::String("value1".into()));
+    test_map.insert(Value::String("key2".into()), Value::String("value2".into()));
     let value: Value = Value::Map(test_map);
     assert_eq!(vec![0xa2, 0x64, 0x6b, 0x65, 0x79, 0x31, 0x66, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x31, 0x64, 0x6b, 0x65, 0x79, 0x32, 0x66, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x32], to_bytes(value));
 }
 
+#[test]
+fn serialize_map_with_integer_keys() {
+    let mut test_map = BTreeMap::new();
+    test_map.insert(Value::Int(1), Value::String("a".into()));
+    test_map.insert(Value::Int(2), Value::String("b".into()));
+    let value: Value = Value::Map(test_map);
+    assert_eq!(vec![0xa2, 0x01, 0x61, 0x61, 0x02, 0x61, 0x62], to_bytes(value));
+}
+
 #[test]
 fn serialize_array() {
     let value = Value::Array(vec![Value::Int(1), Value::Int(2), Value::Int(3)]);
@@ -118,3 +249,97 @@ fn serialize_null() {
     let value = Value::Null;
     assert_eq!(vec![246], to_bytes(value));
 }
+
+#[test]
+fn serialize_bool() {
+    assert_eq!(vec![0xf4], to_bytes(Value::Bool(false)));
+    assert_eq!(vec![0xf5], to_bytes(Value::Bool(true)));
+}
+
+#[test]
+fn serialize_u64() {
+    let value = Value::Int(0x100000000);
+    assert_eq!(vec![27, 0, 0, 0, 1, 0, 0, 0, 0], to_bytes(value));
+}
+
+#[test]
+fn serialize_negative_int() {
+    let value = Value::NegInt(-1);
+    assert_eq!(vec![0x20], to_bytes(value));
+}
+
+#[test]
+fn serialize_negative_int_u16() {
+    let value = Value::NegInt(-0x101);
+    assert_eq!(vec![0x39, 1, 0], to_bytes(value));
+}
+
+#[test]
+fn serialize_float_shrinks_to_f32() {
+    let value = Value::Float(100000.0);
+    assert_eq!(vec![0xfa, 0x47, 0xc3, 0x50, 0x00], to_bytes(value));
+}
+
+#[test]
+fn serialize_float_f64() {
+    let value = Value::Float(1.1);
+    assert_eq!(vec![0xfb, 0x3f, 0xf1, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a], to_bytes(value));
+}
+
+#[test]
+fn serialize_canonical_sorts_map_keys_by_encoded_length() {
+    let mut test_map = BTreeMap::new();
+    test_map.insert(Value::String("aa".into()), Value::Int(1));
+    test_map.insert(Value::String("b".into()), Value::Int(2));
+    let value = Value::Map(test_map);
+    assert_eq!(
+        vec![0xa2, 0x61, 0x62, 0x02, 0x62, 0x61, 0x61, 0x01],
+        to_bytes_canonical(value)
+    );
+}
+
+#[test]
+fn serialize_canonical_sorts_map_keys_across_major_types() {
+    let mut test_map = BTreeMap::new();
+    test_map.insert(Value::NegInt(-24), Value::Int(1));
+    test_map.insert(Value::Int(1000), Value::Int(2));
+    let value = Value::Map(test_map);
+    assert_eq!(
+        vec![0xa2, 0x37, 0x01, 0x19, 0x03, 0xe8, 0x02],
+        to_bytes_canonical(value)
+    );
+}
+
+#[test]
+fn serialize_canonical_nested_map() {
+    let mut inner_map = BTreeMap::new();
+    inner_map.insert(Value::String("aa".into()), Value::Int(1));
+    inner_map.insert(Value::String("b".into()), Value::Int(2));
+    let value = Value::Array(vec![Value::Map(inner_map)]);
+    assert_eq!(
+        vec![0x81, 0xa2, 0x61, 0x62, 0x02, 0x62, 0x61, 0x61, 0x01],
+        to_bytes_canonical(value)
+    );
+}
+
+#[test]
+fn serialize_indefinite_seq() {
+    let mut output = Vec::new();
+    {
+        let mut serializer = VecSerializer::from_vec_writer(VecWriter{output: &mut output});
+        serializer.serialize_indefinite_seq_start();
+        serializer.serialize_unsigned(1, 0b000);
+        serializer.serialize_unsigned(2, 0b000);
+        serializer.serialize_break();
+    }
+    assert_eq!(vec![0x9f, 0x01, 0x02, 0xff], output);
+}
+
+#[test]
+fn serialize_tag() {
+    let value = Value::Tag(0, Box::new(Value::String("2013-03-21T20:04:00Z".into())));
+    assert_eq!(vec![
+        0xc0, 0x74, 0x32, 0x30, 0x31, 0x33, 0x2d, 0x30, 0x33, 0x2d, 0x32, 0x31, 0x54, 0x32, 0x30,
+        0x3a, 0x30, 0x34, 0x3a, 0x30, 0x30, 0x5a,
+    ], to_bytes(value));
+}
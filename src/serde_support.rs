@@ -0,0 +1,672 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use de;
+use error::Error;
+use io::VecWriter;
+use ser::{Serializer as CrateSerializer, VecSerializer};
+use value::Value;
+
+use serde::ser::{
+    Serialize, Serializer,
+    SerializeSeq, SerializeTuple, SerializeTupleStruct, SerializeTupleVariant,
+    SerializeMap, SerializeStruct, SerializeStructVariant,
+};
+use serde::de::{
+    Deserialize, DeserializeOwned, DeserializeSeed, Deserializer as SerdeDeserializer,
+    EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor,
+};
+#[cfg(test)]
+use serde::de::IgnoredAny;
+
+#[derive(Debug)]
+pub enum SerdeError {
+    Cbor(Error),
+    Message(String),
+}
+
+impl fmt::Display for SerdeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SerdeError::Cbor(err) => write!(f, "{:?}", err),
+            SerdeError::Message(ref message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<Error> for SerdeError {
+    fn from(err: Error) -> SerdeError {
+        SerdeError::Cbor(err)
+    }
+}
+
+impl serde::ser::Error for SerdeError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        SerdeError::Message(message.to_string())
+    }
+}
+
+impl serde::de::Error for SerdeError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        SerdeError::Message(message.to_string())
+    }
+}
+
+pub type Result<T> = ::core::result::Result<T, SerdeError>;
+
+pub fn to_vec<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    let mut output = Vec::with_capacity(128);
+    {
+        let mut serializer = CborSerializer {
+            ser: VecSerializer{writer: VecWriter{output: &mut output}},
+        };
+        value.serialize(&mut serializer)?;
+    }
+    Ok(output)
+}
+
+pub fn from_slice<T: DeserializeOwned>(input: &[u8]) -> Result<T> {
+    let value = de::from_bytes(input.to_vec())?;
+    T::deserialize(ValueDeserializer{value: value})
+}
+
+pub struct CborSerializer<'a> {
+    ser: VecSerializer<'a>,
+}
+
+impl<'a, 'b> Serializer for &'a mut CborSerializer<'b> {
+    type Ok = ();
+    type Error = SerdeError;
+    type SerializeSeq = CollectionSerializer<'a, 'b>;
+    type SerializeTuple = CollectionSerializer<'a, 'b>;
+    type SerializeTupleStruct = CollectionSerializer<'a, 'b>;
+    type SerializeTupleVariant = CollectionSerializer<'a, 'b>;
+    type SerializeMap = CollectionSerializer<'a, 'b>;
+    type SerializeStruct = CollectionSerializer<'a, 'b>;
+    type SerializeStructVariant = CollectionSerializer<'a, 'b>;
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.ser.serialize_simple(if v { 21 } else { 20 });
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<()> { self.serialize_i64(v as i64) }
+    fn serialize_i16(self, v: i16) -> Result<()> { self.serialize_i64(v as i64) }
+    fn serialize_i32(self, v: i32) -> Result<()> { self.serialize_i64(v as i64) }
+
+    fn serialize_i64(self, v: i64) -> Result<()> {
+        if v < 0 {
+            self.ser.serialize_unsigned((-1 - v) as u64, 0b001);
+        } else {
+            self.ser.serialize_unsigned(v as u64, 0b000);
+        }
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<()> { self.serialize_u64(v as u64) }
+    fn serialize_u16(self, v: u16) -> Result<()> { self.serialize_u64(v as u64) }
+    fn serialize_u32(self, v: u32) -> Result<()> { self.serialize_u64(v as u64) }
+
+    fn serialize_u64(self, v: u64) -> Result<()> {
+        self.ser.serialize_unsigned(v, 0b000);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<()> { self.serialize_f64(v as f64) }
+
+    fn serialize_f64(self, v: f64) -> Result<()> {
+        self.ser.serialize_float(v);
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<()> {
+        let mut buf = String::new();
+        buf.push(v);
+        self.serialize_str(&buf)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.ser.serialize_string(&String::from(v));
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.ser.serialize_unsigned(v.len() as u64, 0b010);
+        self.ser.serialize_bytes(v.to_vec());
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.ser.serialize_simple(22);
+        Ok(())
+    }
+
+    fn serialize_some<T: ?Sized>(self, value: &T) -> Result<()> where T: Serialize {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        self.ser.serialize_simple(22);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self, _name: &'static str, _variant_index: u32, variant: &'static str,
+    ) -> Result<()> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized>(
+        self, _name: &'static str, value: &T,
+    ) -> Result<()> where T: Serialize {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized>(
+        self, _name: &'static str, _variant_index: u32, variant: &'static str, value: &T,
+    ) -> Result<()> where T: Serialize {
+        self.ser.serialize_map(1);
+        self.ser.serialize_string(&String::from(variant));
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        match len {
+            Some(len) => self.ser.serialize_seq(len),
+            None => self.ser.serialize_indefinite_seq_start(),
+        }
+        Ok(CollectionSerializer{ser: self, indefinite: len.is_none()})
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        self.ser.serialize_seq(len);
+        Ok(CollectionSerializer{ser: self, indefinite: false})
+    }
+
+    fn serialize_tuple_struct(
+        self, _name: &'static str, len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_tuple_variant(
+        self, _name: &'static str, _variant_index: u32, variant: &'static str, len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        self.ser.serialize_map(1);
+        self.ser.serialize_string(&String::from(variant));
+        self.ser.serialize_seq(len);
+        Ok(CollectionSerializer{ser: self, indefinite: false})
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
+        match len {
+            Some(len) => self.ser.serialize_map(len),
+            None => self.ser.serialize_indefinite_map_start(),
+        }
+        Ok(CollectionSerializer{ser: self, indefinite: len.is_none()})
+    }
+
+    fn serialize_struct(
+        self, _name: &'static str, len: usize,
+    ) -> Result<Self::SerializeStruct> {
+        self.ser.serialize_map(len);
+        Ok(CollectionSerializer{ser: self, indefinite: false})
+    }
+
+    fn serialize_struct_variant(
+        self, _name: &'static str, _variant_index: u32, variant: &'static str, len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        self.ser.serialize_map(1);
+        self.ser.serialize_string(&String::from(variant));
+        self.ser.serialize_map(len);
+        Ok(CollectionSerializer{ser: self, indefinite: false})
+    }
+}
+
+pub struct CollectionSerializer<'a, 'b: 'a> {
+    ser: &'a mut CborSerializer<'b>,
+    indefinite: bool,
+}
+
+impl<'a, 'b> SerializeSeq for CollectionSerializer<'a, 'b> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<()> where T: Serialize {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<()> {
+        if self.indefinite {
+            self.ser.ser.serialize_break();
+        }
+        Ok(())
+    }
+}
+
+impl<'a, 'b> SerializeTuple for CollectionSerializer<'a, 'b> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<()> where T: Serialize {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> SerializeTupleStruct for CollectionSerializer<'a, 'b> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<()> where T: Serialize {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> SerializeTupleVariant for CollectionSerializer<'a, 'b> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<()> where T: Serialize {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> SerializeMap for CollectionSerializer<'a, 'b> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<()> where T: Serialize {
+        key.serialize(&mut *self.ser)
+    }
+
+    fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<()> where T: Serialize {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<()> {
+        if self.indefinite {
+            self.ser.ser.serialize_break();
+        }
+        Ok(())
+    }
+}
+
+impl<'a, 'b> SerializeStruct for CollectionSerializer<'a, 'b> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T: ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> where T: Serialize {
+        self.ser.ser.serialize_string(&String::from(key));
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> SerializeStructVariant for CollectionSerializer<'a, 'b> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T: ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> where T: Serialize {
+        self.ser.ser.serialize_string(&String::from(key));
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+pub struct ValueDeserializer {
+    value: Value,
+}
+
+impl<'de> SerdeDeserializer<'de> for ValueDeserializer {
+    type Error = SerdeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        match self.value {
+            Value::Int(n) => visitor.visit_u64(n),
+            Value::NegInt(n) => visitor.visit_i64(n),
+            Value::Float(n) => visitor.visit_f64(n),
+            Value::String(string) => visitor.visit_string(string),
+            Value::Bytes(bytes) => visitor.visit_byte_buf(bytes),
+            Value::Array(values) => visitor.visit_seq(SeqDeserializer{iter: values.into_iter()}),
+            Value::Map(map) => visitor.visit_map(MapDeserializer{iter: map.into_iter(), value: None}),
+            Value::Tag(_, inner) => ValueDeserializer{value: *inner}.deserialize_any(visitor),
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(b) => visitor.visit_bool(b),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self, _name: &'static str, visitor: V,
+    ) -> Result<V::Value> where V: Visitor<'de> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self, _name: &'static str, _variants: &'static [&'static str], visitor: V,
+    ) -> Result<V::Value> where V: Visitor<'de> {
+        match self.value {
+            Value::String(variant) => visitor.visit_enum(EnumDeserializer{variant: variant, value: None}),
+            Value::Map(map) => {
+                if map.len() != 1 {
+                    return Err(SerdeError::Message("expected a single-entry map for an enum".to_string()));
+                }
+                let (key, value) = map.into_iter().next().unwrap();
+                let variant = key.as_string().cloned()
+                    .ok_or_else(|| SerdeError::Message("expected a string enum tag".to_string()))?;
+                visitor.visit_enum(EnumDeserializer{variant: variant, value: Some(value)})
+            },
+            _ => Err(SerdeError::Message("expected a string or map for an enum".to_string())),
+        }
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_tuple_struct<V>(self, _name: &'static str, _len: usize, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_struct<V>(self, _name: &'static str, _fields: &'static [&'static str], visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> { self.deserialize_any(visitor) }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+struct SeqDeserializer {
+    iter: ::alloc::vec::IntoIter<Value>,
+}
+
+impl<'de> SeqAccess<'de> for SeqDeserializer {
+    type Error = SerdeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>> where T: DeserializeSeed<'de> {
+        match self.iter.next() {
+            Some(value) => seed.deserialize(ValueDeserializer{value: value}).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        let (lower, upper) = self.iter.size_hint();
+        if upper == Some(lower) { Some(lower) } else { None }
+    }
+}
+
+struct MapDeserializer {
+    iter: ::alloc::btree_map::IntoIter<Value, Value>,
+    value: Option<Value>,
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer {
+    type Error = SerdeError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>> where K: DeserializeSeed<'de> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(ValueDeserializer{value: key}).map(Some)
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value> where V: DeserializeSeed<'de> {
+        match self.value.take() {
+            Some(value) => seed.deserialize(ValueDeserializer{value: value}),
+            None => Err(SerdeError::Message("value is missing".to_string())),
+        }
+    }
+}
+
+struct EnumDeserializer {
+    variant: String,
+    value: Option<Value>,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer {
+    type Error = SerdeError;
+    type Variant = VariantDeserializer;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant)> where V: DeserializeSeed<'de> {
+        let deserializer = ValueDeserializer{value: Value::String(self.variant)};
+        let value = seed.deserialize(deserializer)?;
+        Ok((value, VariantDeserializer{value: self.value}))
+    }
+}
+
+struct VariantDeserializer {
+    value: Option<Value>,
+}
+
+impl<'de> VariantAccess<'de> for VariantDeserializer {
+    type Error = SerdeError;
+
+    fn unit_variant(self) -> Result<()> {
+        match self.value {
+            None => Ok(()),
+            Some(_) => Err(SerdeError::Message("expected a unit variant".to_string())),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value> where T: DeserializeSeed<'de> {
+        match self.value {
+            Some(value) => seed.deserialize(ValueDeserializer{value: value}),
+            None => Err(SerdeError::Message("expected a newtype variant value".to_string())),
+        }
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        match self.value {
+            Some(Value::Array(values)) => visitor.visit_seq(SeqDeserializer{iter: values.into_iter()}),
+            _ => Err(SerdeError::Message("expected a tuple variant".to_string())),
+        }
+    }
+
+    fn struct_variant<V>(
+        self, _fields: &'static [&'static str], visitor: V,
+    ) -> Result<V::Value> where V: Visitor<'de> {
+        match self.value {
+            Some(Value::Map(map)) => visitor.visit_map(MapDeserializer{iter: map.into_iter(), value: None}),
+            _ => Err(SerdeError::Message("expected a struct variant".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[cfg(test)]
+impl Serialize for Point {
+    fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error> where S: Serializer {
+        let mut state = serializer.serialize_struct("Point", 2)?;
+        state.serialize_field("x", &self.x)?;
+        state.serialize_field("y", &self.y)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+impl<'de> Deserialize<'de> for Point {
+    fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error> where D: SerdeDeserializer<'de> {
+        deserializer.deserialize_map(PointVisitor)
+    }
+}
+
+#[cfg(test)]
+struct PointVisitor;
+
+#[cfg(test)]
+impl<'de> Visitor<'de> for PointVisitor {
+    type Value = Point;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a map with x and y fields")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> ::core::result::Result<Point, A::Error> where A: MapAccess<'de> {
+        let mut x = None;
+        let mut y = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "x" => x = Some(map.next_value()?),
+                "y" => y = Some(map.next_value()?),
+                _ => { let _: IgnoredAny = map.next_value()?; },
+            }
+        }
+
+        Ok(Point {
+            x: x.ok_or_else(|| serde::de::Error::missing_field("x"))?,
+            y: y.ok_or_else(|| serde::de::Error::missing_field("y"))?,
+        })
+    }
+}
+
+#[cfg(test)]
+enum Shape {
+    Unit,
+    Radius(f64),
+}
+
+#[cfg(test)]
+impl Serialize for Shape {
+    fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error> where S: Serializer {
+        match *self {
+            Shape::Unit => serializer.serialize_unit_variant("Shape", 0, "Unit"),
+            Shape::Radius(radius) => serializer.serialize_newtype_variant("Shape", 1, "Radius", &radius),
+        }
+    }
+}
+
+#[cfg(test)]
+impl<'de> Deserialize<'de> for Shape {
+    fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error> where D: SerdeDeserializer<'de> {
+        deserializer.deserialize_enum("Shape", &["Unit", "Radius"], ShapeVisitor)
+    }
+}
+
+#[cfg(test)]
+struct ShapeVisitor;
+
+#[cfg(test)]
+impl<'de> Visitor<'de> for ShapeVisitor {
+    type Value = Shape;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a Shape enum")
+    }
+
+    fn visit_enum<A>(self, data: A) -> ::core::result::Result<Shape, A::Error> where A: EnumAccess<'de> {
+        let (variant, access): (String, _) = data.variant()?;
+
+        match variant.as_str() {
+            "Unit" => {
+                access.unit_variant()?;
+                Ok(Shape::Unit)
+            },
+            "Radius" => Ok(Shape::Radius(access.newtype_variant()?)),
+            _ => Err(serde::de::Error::unknown_variant(&variant, &["Unit", "Radius"])),
+        }
+    }
+}
+
+#[test]
+fn roundtrip_bool() {
+    assert_eq!(true, from_slice::<bool>(&to_vec(&true).unwrap()).unwrap());
+    assert_eq!(false, from_slice::<bool>(&to_vec(&false).unwrap()).unwrap());
+}
+
+#[test]
+fn roundtrip_integers() {
+    assert_eq!(42i32, from_slice::<i32>(&to_vec(&42i32).unwrap()).unwrap());
+    assert_eq!(-42i64, from_slice::<i64>(&to_vec(&-42i64).unwrap()).unwrap());
+}
+
+#[test]
+fn roundtrip_string() {
+    let value = String::from("hello");
+    assert_eq!(value, from_slice::<String>(&to_vec(&value).unwrap()).unwrap());
+}
+
+#[test]
+fn roundtrip_option() {
+    assert_eq!(Some(7i32), from_slice::<Option<i32>>(&to_vec(&Some(7i32)).unwrap()).unwrap());
+    assert_eq!(None, from_slice::<Option<i32>>(&to_vec(&(None as Option<i32>)).unwrap()).unwrap());
+}
+
+#[test]
+fn roundtrip_struct() {
+    let point = Point{x: 1, y: -2};
+    let decoded: Point = from_slice(&to_vec(&point).unwrap()).unwrap();
+    assert_eq!(point.x, decoded.x);
+    assert_eq!(point.y, decoded.y);
+}
+
+#[test]
+fn roundtrip_enum_unit_variant() {
+    let decoded: Shape = from_slice(&to_vec(&Shape::Unit).unwrap()).unwrap();
+    match decoded {
+        Shape::Unit => (),
+        _ => panic!("expected Shape::Unit"),
+    }
+}
+
+#[test]
+fn roundtrip_enum_newtype_variant() {
+    let decoded: Shape = from_slice(&to_vec(&Shape::Radius(2.5)).unwrap()).unwrap();
+    match decoded {
+        Shape::Radius(radius) => assert_eq!(2.5, radius),
+        _ => panic!("expected Shape::Radius"),
+    }
+}
@@ -0,0 +1,64 @@
+use alloc::boxed::Box;
+use alloc::btree_map::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use ser::{Serialize, Serializer};
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Int(u64),
+    NegInt(i64),
+    Float(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    Array(Vec<Value>),
+    Map(BTreeMap<Value, Value>),
+    Tag(u64, Box<Value>),
+    Null,
+    Bool(bool),
+}
+
+impl Value {
+    pub fn as_string(&self) -> Option<&String> {
+        match *self {
+            Value::String(ref string) => Some(string),
+            _ => None,
+        }
+    }
+}
+
+impl_value_ord!(; Value ; Value);
+
+impl Serialize for Value {
+    fn serialize<S>(&mut self, serializer: &mut S) where S: Serializer {
+        match *self {
+            Value::Int(n) => serializer.serialize_unsigned(n, 0b000),
+            Value::NegInt(n) => serializer.serialize_unsigned((-1 - n) as u64, 0b001),
+            Value::Float(n) => serializer.serialize_float(n),
+            Value::String(ref string) => serializer.serialize_string(string),
+            Value::Bytes(ref bytes) => {
+                serializer.serialize_unsigned(bytes.len() as u64, 0b010);
+                serializer.serialize_bytes(bytes.clone());
+            },
+            Value::Array(ref mut values) => {
+                serializer.serialize_seq(values.len());
+                for value in values.iter_mut() {
+                    value.serialize(serializer);
+                }
+            },
+            Value::Map(ref mut map) => {
+                serializer.serialize_map(map.len());
+                for (key, value) in map.iter_mut() {
+                    key.clone().serialize(serializer);
+                    value.serialize(serializer);
+                }
+            },
+            Value::Tag(tag, ref mut value) => {
+                serializer.serialize_tag(tag);
+                value.serialize(serializer);
+            },
+            Value::Null => serializer.serialize_simple(22),
+            Value::Bool(b) => serializer.serialize_simple(if b { 21 } else { 20 }),
+        }
+    }
+}
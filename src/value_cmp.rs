@@ -0,0 +1,74 @@
+#[cfg(test)]
+use std::mem::transmute;
+#[cfg(not(test))]
+use core::mem::transmute;
+
+pub fn float_bits(value: f64) -> u64 {
+    let bits: u64 = unsafe { transmute(value) };
+
+    if bits & (1 << 63) != 0 {
+        !bits
+    } else {
+        bits | (1 << 63)
+    }
+}
+
+// `Value` and `ValueRef` share the same variant set (one owned, one borrowed), so their
+// rank/Ord/PartialOrd/PartialEq/Eq impls are generated from a single definition here instead
+// of being hand-duplicated in both value.rs and value_ref.rs.
+macro_rules! impl_value_ord {
+    ($(<$lt:lifetime>)* ; $Name:ident ; $Ty:ty) => {
+        fn rank $(<$lt>)* (value: &$Ty) -> u8 {
+            match *value {
+                $Name::Int(_) => 0,
+                $Name::NegInt(_) => 1,
+                $Name::Float(_) => 2,
+                $Name::String(_) => 3,
+                $Name::Bytes(_) => 4,
+                $Name::Array(_) => 5,
+                $Name::Map(_) => 6,
+                $Name::Tag(..) => 7,
+                $Name::Null => 8,
+                $Name::Bool(_) => 9,
+            }
+        }
+
+        impl $(<$lt>)* ::core::cmp::Ord for $Ty {
+            fn cmp(&self, other: &$Ty) -> ::core::cmp::Ordering {
+                use core::cmp::Ordering;
+
+                match (self, other) {
+                    (&$Name::Int(a), &$Name::Int(b)) => a.cmp(&b),
+                    (&$Name::NegInt(a), &$Name::NegInt(b)) => a.cmp(&b),
+                    (&$Name::Float(a), &$Name::Float(b)) => {
+                        ::value_cmp::float_bits(a).cmp(&::value_cmp::float_bits(b))
+                    },
+                    (&$Name::String(ref a), &$Name::String(ref b)) => a.cmp(b),
+                    (&$Name::Bytes(ref a), &$Name::Bytes(ref b)) => a.cmp(b),
+                    (&$Name::Array(ref a), &$Name::Array(ref b)) => a.cmp(b),
+                    (&$Name::Map(ref a), &$Name::Map(ref b)) => a.cmp(b),
+                    (&$Name::Tag(tag_a, ref a), &$Name::Tag(tag_b, ref b)) => {
+                        (tag_a, a).cmp(&(tag_b, b))
+                    },
+                    (&$Name::Null, &$Name::Null) => Ordering::Equal,
+                    (&$Name::Bool(a), &$Name::Bool(b)) => a.cmp(&b),
+                    _ => rank(self).cmp(&rank(other)),
+                }
+            }
+        }
+
+        impl $(<$lt>)* ::core::cmp::PartialOrd for $Ty {
+            fn partial_cmp(&self, other: &$Ty) -> Option<::core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl $(<$lt>)* ::core::cmp::PartialEq for $Ty {
+            fn eq(&self, other: &$Ty) -> bool {
+                self.cmp(other) == ::core::cmp::Ordering::Equal
+            }
+        }
+
+        impl $(<$lt>)* ::core::cmp::Eq for $Ty {}
+    };
+}
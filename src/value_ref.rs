@@ -0,0 +1,28 @@
+use alloc::boxed::Box;
+use alloc::btree_map::BTreeMap;
+use alloc::vec::Vec;
+
+#[derive(Debug, Clone)]
+pub enum ValueRef<'a> {
+    Int(u64),
+    NegInt(i64),
+    Float(f64),
+    String(&'a str),
+    Bytes(&'a [u8]),
+    Array(Vec<ValueRef<'a>>),
+    Map(BTreeMap<ValueRef<'a>, ValueRef<'a>>),
+    Tag(u64, Box<ValueRef<'a>>),
+    Null,
+    Bool(bool),
+}
+
+impl<'a> ValueRef<'a> {
+    pub fn as_str(&self) -> Option<&'a str> {
+        match *self {
+            ValueRef::String(string) => Some(string),
+            _ => None,
+        }
+    }
+}
+
+impl_value_ord!(<'a> ; ValueRef ; ValueRef<'a>);